@@ -0,0 +1,198 @@
+//! Temperature-driven fan/pump curve controller.
+//!
+//! Turns [`NZXTDevice::get_status`](crate::NZXTDevice::get_status) plus
+//! [`set_fan_duty`](crate::NZXTDevice::set_fan_duty) /
+//! [`set_pump_duty`](crate::NZXTDevice::set_pump_duty) into a closed control loop: read the
+//! liquid temperature, interpolate the target duty from a user-defined curve, and apply it.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use color_eyre::eyre::Result;
+
+use crate::NZXTDevice;
+
+// The device only accepts duty values in this (inclusive) range.
+const MIN_DUTY: u8 = 20;
+const MAX_DUTY: u8 = 100;
+
+// Default minimum change, in duty percent, before a new value is pushed to the device.
+const DEFAULT_HYSTERESIS: u8 = 3;
+
+/// The channel a [`CurveController`] drives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    Fan,
+    Pump,
+}
+
+/// A single point on a curve: at `temp_c` degrees Celsius, run at `duty` percent.
+#[derive(Debug, Clone, Copy)]
+pub struct CurvePoint {
+    pub temp_c: i32,
+    pub duty: u8,
+}
+
+/// A closed-loop controller mapping liquid temperature to a fan or pump duty.
+pub struct CurveController<'a, 'b> {
+    device: &'b NZXTDevice<'a>,
+    channel: Channel,
+    points: Vec<CurvePoint>,
+    hysteresis: u8,
+    last_applied: Option<u8>,
+    stop: Arc<AtomicBool>,
+}
+
+impl<'a, 'b> CurveController<'a, 'b> {
+    /// Create a controller for the given `channel` from a list of curve points.
+    ///
+    /// Points do not need to be pre-sorted; they are ordered by temperature on construction.
+    pub fn new(device: &'b NZXTDevice<'a>, channel: Channel, points: Vec<CurvePoint>) -> Self {
+        let mut points = points;
+        points.sort_by_key(|p| p.temp_c);
+
+        CurveController {
+            device,
+            channel,
+            points,
+            hysteresis: DEFAULT_HYSTERESIS,
+            last_applied: None,
+            stop: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Set the hysteresis band (in duty percent). The duty is only changed when the interpolated
+    /// target differs from the last-applied value by more than this amount.
+    pub fn with_hysteresis(mut self, hysteresis: u8) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+
+    /// A shared stop flag. Setting it to `true` ends an in-progress [`run_loop`](Self::run_loop).
+    pub fn stop_flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.stop)
+    }
+
+    /// Read the current liquid temperature, interpolate the target duty and apply it if it has
+    /// moved outside the hysteresis band. Returns the duty that was applied, or `None` if the
+    /// change was too small to act on.
+    pub fn tick(&mut self) -> Result<Option<u8>> {
+        let temp = self.device.get_status()?.temp;
+        let target = interpolate(&self.points, temp);
+
+        if let Some(last) = self.last_applied {
+            if target.abs_diff(last) <= self.hysteresis {
+                return Ok(None);
+            }
+        }
+
+        match self.channel {
+            Channel::Fan => self.device.set_fan_duty(target)?,
+            Channel::Pump => self.device.set_pump_duty(target)?,
+        }
+
+        self.last_applied = Some(target);
+        Ok(Some(target))
+    }
+
+    /// Poll and adjust on a fixed cadence until the [`stop_flag`](Self::stop_flag) is set.
+    pub fn run_loop(&mut self, interval: Duration) -> Result<()> {
+        while !self.stop.load(Ordering::Relaxed) {
+            self.tick()?;
+            thread::sleep(interval);
+        }
+
+        Ok(())
+    }
+}
+
+/// Linearly interpolate the target duty for `temp_c` between the bracketing curve points,
+/// clamping the result to the device's valid [`MIN_DUTY`]..=[`MAX_DUTY`] range.
+///
+/// `points` must be sorted by temperature. Temperatures below the first point clamp to its duty,
+/// temperatures above the last point clamp to the last point's duty.
+fn interpolate(points: &[CurvePoint], temp_c: i32) -> u8 {
+    let duty = match points {
+        [] => MIN_DUTY,
+        [only] => only.duty,
+        _ => {
+            let first = &points[0];
+            let last = &points[points.len() - 1];
+
+            if temp_c <= first.temp_c {
+                first.duty
+            } else if temp_c >= last.temp_c {
+                last.duty
+            } else {
+                // Find the pair of points bracketing temp_c and interpolate between them.
+                let upper = points.iter().position(|p| p.temp_c >= temp_c).unwrap();
+                let lo = &points[upper - 1];
+                let hi = &points[upper];
+
+                let span = (hi.temp_c - lo.temp_c) as f32;
+                let frac = (temp_c - lo.temp_c) as f32 / span;
+                let duty = lo.duty as f32 + frac * (hi.duty as f32 - lo.duty as f32);
+
+                duty.round() as u8
+            }
+        }
+    };
+
+    duty.clamp(MIN_DUTY, MAX_DUTY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve() -> Vec<CurvePoint> {
+        vec![
+            CurvePoint {
+                temp_c: 30,
+                duty: 20,
+            },
+            CurvePoint {
+                temp_c: 40,
+                duty: 50,
+            },
+            CurvePoint {
+                temp_c: 50,
+                duty: 100,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_interpolate_below_and_above_range() {
+        let points = curve();
+
+        // Below the first point clamps to its duty, above the last clamps to the last's.
+        assert_eq!(interpolate(&points, 10), 20);
+        assert_eq!(interpolate(&points, 80), 100);
+    }
+
+    #[test]
+    fn test_interpolate_between_points() {
+        let points = curve();
+
+        // Exactly on a point.
+        assert_eq!(interpolate(&points, 40), 50);
+
+        // Halfway between 30 and 40 => halfway between 20 and 50.
+        assert_eq!(interpolate(&points, 35), 35);
+    }
+
+    #[test]
+    fn test_interpolate_clamps_to_valid_duty() {
+        let points = vec![CurvePoint {
+            temp_c: 30,
+            duty: 5,
+        }];
+
+        // A curve asking for less than the device minimum is clamped up.
+        assert_eq!(interpolate(&points, 30), MIN_DUTY);
+    }
+}
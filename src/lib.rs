@@ -1,15 +1,30 @@
 #[cfg(not(target_os = "windows"))]
+use std::fs::File;
+#[cfg(not(target_os = "windows"))]
+use std::io::BufReader;
+#[cfg(not(target_os = "windows"))]
 use std::path::Path;
 use std::time::Duration;
 
 use color_eyre::eyre::eyre;
 use color_eyre::eyre::Result;
 #[cfg(not(target_os = "windows"))]
+use image::codecs::gif::GifDecoder;
+#[cfg(not(target_os = "windows"))]
+use image::AnimationDecoder;
+#[cfg(not(target_os = "windows"))]
+use image::DynamicImage;
+#[cfg(not(target_os = "windows"))]
 use image::GenericImageView;
 use mockall::*;
+use rusb::Device;
+use rusb::DeviceDescriptor;
 use rusb::DeviceHandle;
 use rusb::UsbContext;
 
+pub mod curve;
+pub mod profile;
+
 // Kraken Z series.
 pub const VID: u16 = 0x1e71;
 pub const PID: u16 = 0x3008;
@@ -25,6 +40,14 @@ const READ_LENGTH: usize = 64;
 const WRITE_TIMEOUT: Duration = std::time::Duration::from_secs(10);
 const READ_TIMEOUT: Duration = std::time::Duration::from_secs(3);
 
+// The device exposes this many addressable memory buckets (0..=14).
+const NUM_BUCKETS: usize = 15;
+
+// Buckets are spaced this many 1kb memory slots apart on the device. A still fits within one
+// bucket's stride; a larger animation is stored contiguously across the buckets that follow its
+// start index, up to the end of device memory.
+const BUCKET_MEMORY_SLOTS: u16 = 800;
+
 const SETUP_BUCKET: u8 = 0x32;
 const SET_BUCKET: u8 = 0x1;
 const DELETE_BUCKET: u8 = 0x2;
@@ -35,6 +58,11 @@ const WRITE_SETUP: u8 = 0x36;
 const WRITE_START: u8 = 0x1;
 const WRITE_FINISH: u8 = 0x2;
 
+// Visual data mode passed to `send_bulk_data_info`. Stills use a single frame (2),
+// animations stream every decoded frame back-to-back (1).
+const STILL_IMAGE_MODE: u8 = 2;
+const ANIMATION_MODE: u8 = 1;
+
 const INTERRUPT_WRITE_ENDPOINT: u8 = 0x01;
 const INTERRUPT_READ_ENDPOINT: u8 = 0x81;
 const BULK_WRITE_ENDPOINT: u8 = 0x02;
@@ -159,6 +187,60 @@ impl NZXTDevice<'_> {
         Ok(())
     }
 
+    /// Stream `data` to the BULK endpoint in fixed `BULK_WRITE_LENGTH` (512 byte) blocks.
+    ///
+    /// Each block is zero-padded to the full block length (as `write_bulk` already does), the
+    /// returned byte counts are summed, and a short write re-issues the unacknowledged remainder
+    /// on the next iteration. `progress` is invoked after every block with `(bytes_sent, total)`
+    /// so callers can drive an upload bar. Errors if the cumulative count ever stalls or the final
+    /// total does not match the buffer length.
+    fn write_bulk_chunked(
+        &self,
+        data: &[u8],
+        mut progress: Option<&mut dyn FnMut(usize, usize)>,
+    ) -> Result<()> {
+        let total = data.len();
+        let mut sent = 0;
+
+        while sent < total {
+            let end = (sent + BULK_WRITE_LENGTH).min(total);
+            let chunk = &data[sent..end];
+
+            let mut buf = [0u8; BULK_WRITE_LENGTH];
+            buf[..chunk.len()].copy_from_slice(chunk);
+
+            let written = self
+                .handle
+                .write_bulk(BULK_WRITE_ENDPOINT, &buf, WRITE_TIMEOUT)?;
+
+            if written == 0 {
+                return Err(eyre!(
+                    "Bulk transfer stalled after {} of {} bytes",
+                    sent,
+                    total
+                ));
+            }
+
+            // Advance by the acknowledged byte count, capped to the real (unpadded) chunk length;
+            // anything short is left for the next iteration to re-send.
+            sent += written.min(chunk.len());
+
+            if let Some(cb) = progress.as_mut() {
+                cb(sent, total);
+            }
+        }
+
+        if sent != total {
+            return Err(eyre!(
+                "Bulk transfer incomplete: wrote {} of {} bytes",
+                sent,
+                total
+            ));
+        }
+
+        Ok(())
+    }
+
     /// Read 64 bytes (READ_LENGTH) from the device.
     fn read(&self) -> Result<Vec<u8>> {
         let mut buf = [0u8; READ_LENGTH];
@@ -280,16 +362,83 @@ impl NZXTDevice<'_> {
 
     /// Set the device LCD to an image. Will be resized if it does not have height or width of 320px
     /// Will rotate to the NZXTDevice rotation_degrees amount prior to uploading.
-    /// Does NOT support gif.
+    /// Animated GIFs are detected by extension and routed through [`set_animation`](Self::set_animation).
     #[cfg(not(target_os = "windows"))]
     pub fn set_image(
         &self,
         path_to_image: &Path,
         index: u8,
         apply_after_upload: bool,
+        progress: Option<&mut dyn FnMut(usize, usize)>,
+    ) -> Result<()> {
+        // Hand animated GIFs off to the multi-frame path, letting the firmware
+        // pick the playback rate from the file's own timing.
+        if is_gif(path_to_image) {
+            return self.set_animation(path_to_image, index, 0, apply_after_upload, progress);
+        }
+
+        let img = self.prepare_frame(image::open(path_to_image)?);
+        let image_bytes = img.to_rgba8().into_raw();
+        let image_size_bytes = image_bytes.len() as i32;
+
+        self.upload_image(
+            &image_bytes,
+            image_size_bytes,
+            index,
+            STILL_IMAGE_MODE,
+            apply_after_upload,
+            progress,
+        )
+    }
+
+    /// Set the device LCD to an animated GIF.
+    ///
+    /// Every frame is decoded, rotated and resized with the same pipeline used for stills,
+    /// then concatenated into a single contiguous RGBA8 stream and uploaded to `index`.
+    /// `fps` is the desired playback rate; pass `0` to let the device keep the GIF's own timing.
+    ///
+    /// **Capacity limit:** frames are stored contiguously from `index` to the end of device
+    /// memory, so an animation may span the buckets that follow it. Each 320×320 RGBA frame is
+    /// 400 1kb slots and a bucket spans 800 slots, giving room for `2 * (15 - index)` frames —
+    /// 30 at `index` 0. A GIF needing more is rejected rather than overrun device memory.
+    #[cfg(not(target_os = "windows"))]
+    pub fn set_animation(
+        &self,
+        path_to_gif: &Path,
+        index: u8,
+        fps: u8,
+        apply_after_upload: bool,
+        progress: Option<&mut dyn FnMut(usize, usize)>,
     ) -> Result<()> {
-        let mut img = image::open(path_to_image)?;
+        let decoder = GifDecoder::new(BufReader::new(File::open(path_to_gif)?))?;
+        let frames = decoder.into_frames().collect_frames()?;
+
+        if frames.is_empty() {
+            return Err(eyre!("GIF contains no frames"));
+        }
+
+        // Apply the still pipeline to each frame and concatenate the raw buffers.
+        let mut image_bytes = Vec::new();
+        for frame in frames {
+            let img = self.prepare_frame(DynamicImage::ImageRgba8(frame.into_buffer()));
+            image_bytes.extend_from_slice(&img.to_rgba8().into_raw());
+        }
+
+        let image_size_bytes = image_bytes.len() as i32;
+
+        self.upload_animation(
+            &image_bytes,
+            image_size_bytes,
+            index,
+            fps,
+            apply_after_upload,
+            progress,
+        )
+    }
 
+    /// Apply the rotation and 320x320 resize pipeline shared by stills and animation frames.
+    #[cfg(not(target_os = "windows"))]
+    fn prepare_frame(&self, mut img: DynamicImage) -> DynamicImage {
         let (width, height) = img.dimensions();
 
         if self.rotation_degrees == 90 {
@@ -304,38 +453,113 @@ impl NZXTDevice<'_> {
             img = img.resize_exact(320, 320, image::imageops::FilterType::Gaussian);
         }
 
-        let image_bytes = img.to_rgba8().into_raw();
-        let image_size_bytes = image_bytes.len() as i32;
-
-        self.upload_image(&image_bytes, image_size_bytes, index, apply_after_upload)
+        img
     }
 
-    /// Upload an image (either still or gif) to the device.
+    /// Upload a still image to the device.
     #[cfg(not(target_os = "windows"))]
     fn upload_image(
         &self,
         image_bytes: &[u8],
         image_size_bytes: i32,
         index: u8,
+        mode: u8,
         apply_after_upload: bool,
+        progress: Option<&mut dyn FnMut(usize, usize)>,
+    ) -> Result<()> {
+        self.upload_bucket(
+            image_bytes,
+            image_size_bytes,
+            index,
+            mode,
+            0,
+            apply_after_upload,
+            progress,
+        )
+    }
+
+    /// Upload an animation (concatenated per-frame RGBA buffers) to the device.
+    #[cfg(not(target_os = "windows"))]
+    fn upload_animation(
+        &self,
+        image_bytes: &[u8],
+        image_size_bytes: i32,
+        index: u8,
+        fps: u8,
+        apply_after_upload: bool,
+        progress: Option<&mut dyn FnMut(usize, usize)>,
+    ) -> Result<()> {
+        self.upload_bucket(
+            image_bytes,
+            image_size_bytes,
+            index,
+            ANIMATION_MODE,
+            fps,
+            apply_after_upload,
+            progress,
+        )
+    }
+
+    /// Drive the bucket protocol for a prepared byte stream using the given visual data `mode`
+    /// and playback rate `fps` (`0` keeps the source timing, ignored for stills).
+    #[cfg(not(target_os = "windows"))]
+    fn upload_bucket(
+        &self,
+        image_bytes: &[u8],
+        image_size_bytes: i32,
+        index: u8,
+        mode: u8,
+        fps: u8,
+        apply_after_upload: bool,
+        progress: Option<&mut dyn FnMut(usize, usize)>,
     ) -> Result<()> {
         self.set_blank_screen()?;
         self.delete_bucket(index)?;
 
-        let memory_slot = 800 * index as u16;
-        // Memory slots are in 1kb sections
-        let memory_slot_count = (image_size_bytes / 1024) as u16;
+        if index as usize >= NUM_BUCKETS {
+            return Err(eyre!(
+                "Bucket index {} is out of range 0..{}",
+                index,
+                NUM_BUCKETS
+            ));
+        }
+
+        let memory_slot = BUCKET_MEMORY_SLOTS * index as u16;
+
+        // Memory slots are in 1kb sections; round up so a partial final slot is still reserved.
+        let slot_count = (image_size_bytes as usize).div_ceil(1024);
+
+        // A 320x320 still is 400 slots, but a concatenated multi-frame animation can be larger. It
+        // is laid out contiguously from this bucket to the end of device memory, so it may span
+        // the buckets that follow `index`. Reject anything that would run past the end of that
+        // memory (which would also truncate the slot count into a u16) rather than corrupt it.
+        let available_slots = BUCKET_MEMORY_SLOTS as usize * (NUM_BUCKETS - index as usize);
+        if slot_count > available_slots {
+            return Err(eyre!(
+                "Payload of {} memory slots exceeds the {} slots available from bucket {} to the \
+                 end of device memory; reduce the frame count or start at a lower index",
+                slot_count,
+                available_slots,
+                index
+            ));
+        }
+
+        let memory_slot_count = slot_count as u16;
 
         self.setup_bucket(index, index + 1, memory_slot, memory_slot_count)?;
         self.write_start_bucket(index)?;
-        self.send_bulk_data_info(2)?;
+        self.send_bulk_data_info_for(mode, fps)?;
 
-        // Write image bytes to BULK endpoint.
-        self.handle
-            .write_bulk(BULK_WRITE_ENDPOINT, image_bytes, WRITE_TIMEOUT)?;
+        // Write image bytes to the BULK endpoint in fixed-size blocks.
+        self.write_bulk_chunked(image_bytes, progress)?;
 
         self.write_finish_bucket(index)?;
 
+        // Re-assert the target bucket once the upload is done so the displayed mode reflects the
+        // freshly uploaded asset. This is done synchronously here rather than from a background
+        // keep-alive thread: the device is driven through a single exclusive `&mut` handle, so
+        // there is no sound way to poll or re-assert the session concurrently while this transfer
+        // owns the endpoint.
         if apply_after_upload {
             self.switch_bucket(index)?;
         }
@@ -376,17 +600,26 @@ impl NZXTDevice<'_> {
 
     /// Send the bulk data info for the given mode.
     pub fn send_bulk_data_info(&self, mode: u8) -> Result<()> {
-        // Fill with 12fa01e8abcdef987654321 (magic numbers) and then mode,
-        // couple of 0x00 and then more magic.
+        self.send_bulk_data_info_for(mode, 0)
+    }
+
+    /// Send the bulk data info for the given mode and playback rate.
+    ///
+    /// `fps` is the animation playback rate carried in the reserved byte following `mode`;
+    /// `0` leaves the device on its default (source) timing, which is what stills use.
+    fn send_bulk_data_info_for(&self, mode: u8, fps: u8) -> Result<()> {
+        // Fill with 12fa01e8abcdef987654321 (magic numbers) and then mode, the
+        // playback rate, a couple of 0x00 and then more magic.
         self.write_bulk(&[
-            0x12, 0xfa, 0x01, 0xe8, 0xab, 0xcd, 0xef, 0x98, 0x76, 0x54, 0x32, 0x10, mode, 0x00,
+            0x12, 0xfa, 0x01, 0xe8, 0xab, 0xcd, 0xef, 0x98, 0x76, 0x54, 0x32, 0x10, mode, fps,
             0x00, 0x00, 0x00, 0x40, 0x96,
         ])
     }
 }
 
 impl Drop for NZXTDevice<'_> {
-    /// Upon dropping NZXTDevice, ensure all interfaces are released and the device is reset.
+    /// Upon dropping NZXTDevice, ensure all interfaces are released and the device is reset, in
+    /// that order.
     fn drop(&mut self) {
         #[cfg(not(target_os = "windows"))]
         self.handle
@@ -403,6 +636,100 @@ impl Drop for NZXTDevice<'_> {
     }
 }
 
+/// Return true if the path points at a GIF, matched on its file extension.
+#[cfg(not(target_os = "windows"))]
+fn is_gif(path: &Path) -> bool {
+    path.extension()
+        .map(|ext| ext.eq_ignore_ascii_case("gif"))
+        .unwrap_or(false)
+}
+
+/// Identifying details for a Kraken Z device discovered on the USB bus.
+#[derive(Debug, Clone)]
+pub struct KrakenDescriptor {
+    pub bus_number: u8,
+    pub address: u8,
+    pub serial: Option<String>,
+}
+
+/// Scan the USB bus for every connected Kraken Z device.
+///
+/// Returns each match as its [`KrakenDescriptor`] paired with the underlying `Device`, ready to be
+/// opened. The enumeration order is whatever the bus reports, so prefer selecting by serial when a
+/// deterministic choice matters.
+pub fn discover_devices<T: UsbContext>(context: &T) -> Result<Vec<(KrakenDescriptor, Device<T>)>> {
+    let mut found = Vec::new();
+
+    for device in context.devices()?.iter() {
+        let descriptor = device.device_descriptor()?;
+
+        if descriptor.vendor_id() == VID && descriptor.product_id() == PID {
+            let serial = read_serial(&device, &descriptor);
+
+            found.push((
+                KrakenDescriptor {
+                    bus_number: device.bus_number(),
+                    address: device.address(),
+                    serial,
+                },
+                device,
+            ));
+        }
+    }
+
+    Ok(found)
+}
+
+/// Read the serial number string from a device's descriptor, returning `None` if it cannot be read.
+fn read_serial<T: UsbContext>(device: &Device<T>, descriptor: &DeviceDescriptor) -> Option<String> {
+    let handle = device.open().ok()?;
+    let languages = handle.read_languages(READ_TIMEOUT).ok()?;
+    let language = languages.first()?;
+
+    handle
+        .read_serial_number_string(*language, descriptor, READ_TIMEOUT)
+        .ok()
+}
+
+/// Open a discovered Kraken Z device by its serial string.
+pub fn open_device_by_serial<T: UsbContext>(
+    context: &T,
+    serial: &str,
+) -> Result<DeviceHandle<T>> {
+    for (descriptor, device) in discover_devices(context)? {
+        if descriptor.serial.as_deref() == Some(serial) {
+            return Ok(device.open()?);
+        }
+    }
+
+    Err(eyre!("No Kraken Z device found with serial {}", serial))
+}
+
+/// Open a discovered Kraken Z device by its index in the enumeration order.
+pub fn open_device_by_index<T: UsbContext>(
+    context: &T,
+    index: usize,
+) -> Result<DeviceHandle<T>> {
+    let mut devices = discover_devices(context)?;
+
+    if index >= devices.len() {
+        return Err(eyre!(
+            "No Kraken Z device at index {} (found {})",
+            index,
+            devices.len()
+        ));
+    }
+
+    Ok(devices.remove(index).1.open()?)
+}
+
+/// Open the first discovered Kraken Z device.
+///
+/// Thin wrapper over [`open_device_by_index`] at index `0`, preserving the single-device use case.
+pub fn open_device<T: UsbContext>(context: &T) -> Result<DeviceHandle<T>> {
+    open_device_by_index(context, 0)
+}
+
 /// Parse the returned data bytes from the device into a firmware version.
 fn parse_firmware_info(data: &[u8]) -> String {
     let major = data[0x11];
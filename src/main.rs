@@ -1,26 +1,17 @@
+#[cfg(not(target_os = "windows"))]
 use std::path::Path;
 
 use color_eyre::Result;
 use rusb::Context;
-use zkraken_lib::{open_device, NZXTDevice, PID, VID};
+use zkraken_lib::{open_device, NZXTDevice};
 
 fn main() -> Result<()> {
-    let api = hidapi_rusb::HidApi::new()?;
-    let mut context = Context::new()?;
-    let hid_device = api.open(VID, PID)?;
+    let context = Context::new()?;
 
-    // We need to use RUSB as well because HIDAPI doesn't support the writing to BULK endpoint.
-    let (_, mut handle) =
-        open_device(&mut context, VID, PID).expect("No NZXT Kraken Z device found.");
+    // Open the first Kraken Z device discovered on the USB bus.
+    let mut handle = open_device(&context).expect("No NZXT Kraken Z device found.");
 
-    let mut nzxt_device = NZXTDevice {
-        device: &hid_device,
-        bulk_endpoint_handle: &mut handle,
-        initialised: false,
-        rotation_degrees: 270,
-    };
-
-    nzxt_device.initialise()?;
+    let nzxt_device = NZXTDevice::new(&mut handle, 270)?;
 
     let firmware = nzxt_device.get_firmware_version()?;
     println!("Firmware version: {}", firmware);
@@ -31,9 +22,14 @@ fn main() -> Result<()> {
     nzxt_device.set_fan_duty(80)?;
     nzxt_device.set_pump_duty(80)?;
 
-    let image = Path::new("C:\\Users\\me\\Downloads\\elmo.gif");
-
-    nzxt_device.set_image(image, 3, true)?;
+    // Image upload is only available off Windows (see `set_image`). The animated GIF is uploaded
+    // to bucket 0, which has the whole of device memory ahead of it; see `set_animation` for the
+    // per-index frame ceiling.
+    #[cfg(not(target_os = "windows"))]
+    {
+        let image = Path::new("elmo.gif");
+        nzxt_device.set_image(image, 0, true, None)?;
+    }
 
     Ok(())
 }
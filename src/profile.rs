@@ -0,0 +1,212 @@
+//! Persistent device profiles.
+//!
+//! Serializes a device's desired state — brightness, active visual mode, fan/pump curves and the
+//! uploaded image/animation bucket — to a config file so a daemon can restore the user's last-known
+//! setup after a power cycle, which the device does not survive on its own.
+
+use std::fs;
+use std::path::Path;
+
+use color_eyre::eyre::eyre;
+use color_eyre::eyre::Result;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::curve::Channel;
+use crate::curve::CurveController;
+use crate::curve::CurvePoint;
+use crate::NZXTDevice;
+
+// Bumped whenever the serialized layout changes; older files are migrated up on load.
+const CURRENT_VERSION: u32 = 1;
+
+/// The visual mode the LCD should display.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VisualMode {
+    Blank,
+    LiquidTemp,
+    DualInfographic,
+    Bucket(u8),
+}
+
+/// A serialized device profile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    /// Format version, used to migrate older profiles forward.
+    pub version: u32,
+    pub brightness: u8,
+    pub visual_mode: VisualMode,
+    /// Fan curve points as `(temp_c, duty)` pairs; `None` leaves the fan alone.
+    pub fan_curve: Option<Vec<(i32, u8)>>,
+    /// Pump curve points as `(temp_c, duty)` pairs; `None` leaves the pump alone.
+    pub pump_curve: Option<Vec<(i32, u8)>>,
+    /// Bucket index holding the uploaded image/animation, if any.
+    pub image_index: Option<u8>,
+}
+
+impl Profile {
+    /// Deserialize a profile from the given path, migrating and validating it, then replay every
+    /// setting against `device`. Intended to be called right after [`NZXTDevice::new`].
+    pub fn load_and_apply(device: &NZXTDevice, path: &Path) -> Result<()> {
+        let mut profile: Profile = serde_json::from_str(&fs::read_to_string(path)?)?;
+
+        profile.migrate()?;
+        profile.validate()?;
+        profile.apply(device)
+    }
+
+    /// Serialize this profile to the given path.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Step an older profile up to [`CURRENT_VERSION`], rejecting files newer than we understand.
+    fn migrate(&mut self) -> Result<()> {
+        if self.version > CURRENT_VERSION {
+            return Err(eyre!(
+                "Profile version {} is newer than supported version {}",
+                self.version,
+                CURRENT_VERSION
+            ));
+        }
+
+        // Future migrations step `self.version` up one release at a time here.
+        self.version = CURRENT_VERSION;
+        Ok(())
+    }
+
+    /// Validate every field is within the device's accepted ranges before any writes are issued.
+    fn validate(&self) -> Result<()> {
+        if self.brightness > 100 {
+            return Err(eyre!("Brightness {} is out of range 0..=100", self.brightness));
+        }
+
+        if let VisualMode::Bucket(index) = self.visual_mode {
+            validate_bucket_index(index)?;
+        }
+
+        if let Some(index) = self.image_index {
+            validate_bucket_index(index)?;
+        }
+
+        for (label, curve) in [("fan", &self.fan_curve), ("pump", &self.pump_curve)] {
+            if let Some(points) = curve {
+                for (temp_c, duty) in points {
+                    if !(20..=100).contains(duty) {
+                        return Err(eyre!(
+                            "{} curve duty {} at {}C is out of range 20..=100",
+                            label,
+                            duty,
+                            temp_c
+                        ));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replay the profile against the device. Validation is assumed to have already passed.
+    fn apply(&self, device: &NZXTDevice) -> Result<()> {
+        device.set_brightness(self.brightness)?;
+
+        if let Some(points) = &self.fan_curve {
+            apply_curve(device, Channel::Fan, points)?;
+        }
+
+        if let Some(points) = &self.pump_curve {
+            apply_curve(device, Channel::Pump, points)?;
+        }
+
+        // Re-select the bucket holding the uploaded image/animation so the stored asset is the
+        // active custom bucket again; an explicit visual mode below still decides what is shown.
+        if let Some(index) = self.image_index {
+            device.switch_bucket(index)?;
+        }
+
+        match self.visual_mode {
+            VisualMode::Blank => device.set_blank_screen()?,
+            VisualMode::LiquidTemp => device.set_liquid_temp_mode()?,
+            VisualMode::DualInfographic => device.set_dual_infographic_mode()?,
+            VisualMode::Bucket(index) => device.switch_bucket(index)?,
+        }
+
+        Ok(())
+    }
+}
+
+/// Build a controller from stored points and apply one reading so the duty reflects the curve now.
+fn apply_curve(device: &NZXTDevice, channel: Channel, points: &[(i32, u8)]) -> Result<()> {
+    let points = points
+        .iter()
+        .map(|&(temp_c, duty)| CurvePoint { temp_c, duty })
+        .collect();
+
+    CurveController::new(device, channel, points).tick()?;
+    Ok(())
+}
+
+/// Buckets are addressed 0..=14 on the device.
+fn validate_bucket_index(index: u8) -> Result<()> {
+    if index > 14 {
+        return Err(eyre!("Bucket index {} is out of range 0..=14", index));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn valid_profile() -> Profile {
+        Profile {
+            version: CURRENT_VERSION,
+            brightness: 80,
+            visual_mode: VisualMode::Bucket(3),
+            fan_curve: Some(vec![(30, 20), (50, 100)]),
+            pump_curve: None,
+            image_index: Some(3),
+        }
+    }
+
+    #[test]
+    fn test_validate_accepts_valid_profile() {
+        assert!(valid_profile().validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_out_of_range() {
+        let mut profile = valid_profile();
+        profile.brightness = 101;
+        assert!(profile.validate().is_err());
+
+        let mut profile = valid_profile();
+        profile.visual_mode = VisualMode::Bucket(15);
+        assert!(profile.validate().is_err());
+
+        let mut profile = valid_profile();
+        profile.fan_curve = Some(vec![(30, 10)]);
+        assert!(profile.validate().is_err());
+    }
+
+    #[test]
+    fn test_migrate_rejects_future_version() {
+        let mut profile = valid_profile();
+        profile.version = CURRENT_VERSION + 1;
+        assert!(profile.migrate().is_err());
+    }
+
+    #[test]
+    fn test_roundtrip_serialization() {
+        let profile = valid_profile();
+        let json = serde_json::to_string(&profile).unwrap();
+        let parsed: Profile = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed.brightness, profile.brightness);
+        assert_eq!(parsed.visual_mode, profile.visual_mode);
+        assert_eq!(parsed.fan_curve, profile.fan_curve);
+    }
+}